@@ -2,6 +2,9 @@ use std::error::Error as StdError;
 use std::fmt;
 use std::io;
 
+#[cfg(feature = "backtrace")]
+use std::backtrace::{Backtrace, BacktraceStatus};
+
 use crate::{StatusCode, Url};
 
 /// A `Result` alias where the `Err` case is `reqwest::Error`.
@@ -18,6 +21,8 @@ struct Inner {
     kind: Kind,
     source: Option<BoxError>,
     url: Option<Url>,
+    #[cfg(feature = "backtrace")]
+    backtrace: Option<Backtrace>,
 }
 
 impl Error {
@@ -30,6 +35,15 @@ impl Error {
                 kind,
                 source: source.map(Into::into),
                 url: None,
+                #[cfg(feature = "backtrace")]
+                backtrace: {
+                    let backtrace = Backtrace::capture();
+                    if backtrace.status() == BacktraceStatus::Captured {
+                        Some(backtrace)
+                    } else {
+                        None
+                    }
+                },
             }),
         }
     }
@@ -71,6 +85,33 @@ impl Error {
         }
     }
 
+    /// Returns true if the error occurred while trying to connect, before a
+    /// request could be sent.
+    ///
+    /// This includes DNS failures, TCP connect failures, and TLS handshake
+    /// failures, but not errors occurring after a connection has been
+    /// established. Checks both errors explicitly constructed as
+    /// `Kind::Connect` and errors wrapping a `hyper::Error` whose own
+    /// `is_connect()` says the same, so this stays accurate even for call
+    /// sites that haven't been migrated to the dedicated `Connect` kind yet.
+    pub fn is_connect(&self) -> bool {
+        if let Kind::Connect = self.inner.kind {
+            return true;
+        }
+
+        let mut source = self.source();
+        while let Some(err) = source {
+            if let Some(hyper_err) = err.downcast_ref::<hyper::Error>() {
+                if hyper_err.is_connect() {
+                    return true;
+                }
+            }
+            source = err.source();
+        }
+
+        false
+    }
+
     /// Returns true if the error is from `Response::error_for_status`.
     pub fn is_status(&self) -> bool {
         match self.inner.kind {
@@ -84,6 +125,29 @@ impl Error {
         self.source().map(|e| e.is::<TimedOut>()).unwrap_or(false)
     }
 
+    /// Returns true if this error represents a transient failure that is
+    /// generally safe to retry.
+    ///
+    /// This covers connection-phase failures ([`is_connect`][Error::is_connect]),
+    /// timeouts ([`is_timeout`][Error::is_timeout]), and the hyper-level
+    /// conditions that indicate the request never reached the peer in a way
+    /// that could have side effects: an incomplete message, a canceled
+    /// request, or a closed channel. `Kind::Builder`, `Kind::Decode`,
+    /// `Kind::Status`, and `Kind::Redirect` are never retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self.inner.kind {
+            Kind::Builder | Kind::Decode | Kind::Status(_) | Kind::Redirect => false,
+            _ => {
+                self.is_connect()
+                    || self.is_timeout()
+                    || self
+                        .downcast_ref::<hyper::Error>()
+                        .map(|e| e.is_incomplete_message() || e.is_canceled() || e.is_closed())
+                        .unwrap_or(false)
+            }
+        }
+    }
+
     /// Returns the status code, if the error was generated from a response.
     pub fn status(&self) -> Option<StatusCode> {
         match self.inner.kind {
@@ -92,6 +156,39 @@ impl Error {
         }
     }
 
+    /// Returns a reference to the backtrace captured when this error was created.
+    ///
+    /// Backtrace capture is gated behind the `backtrace` cargo feature, and
+    /// follows the same `RUST_BACKTRACE`/`RUST_LIB_BACKTRACE` rules as
+    /// `std::backtrace::Backtrace::capture`, so this can still be `None` if
+    /// backtraces were not requested by the environment.
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.inner.backtrace.as_ref()
+    }
+
+    /// Returns the underlying cause of this error, if any.
+    ///
+    /// This is the same value `source()` returns, but as an inherent method
+    /// so it's reachable without importing `std::error::Error`.
+    pub fn get_ref(&self) -> Option<&(dyn StdError + 'static)> {
+        self.inner.source.as_ref().map(|e| &**e as _)
+    }
+
+    /// Attempts to downcast the underlying cause to a concrete type.
+    ///
+    /// This is useful for recovering a specific error, such as a
+    /// `serde_json::Error` nested inside a `Kind::Decode` error, without
+    /// matching on its `Display` output.
+    pub fn downcast_ref<T: StdError + 'static>(&self) -> Option<&T> {
+        self.get_ref().and_then(|e| e.downcast_ref::<T>())
+    }
+
+    /// Consumes the error, returning its underlying cause, if any.
+    pub fn into_source(self) -> Option<BoxError> {
+        self.inner.source
+    }
+
     // private
 
     pub(crate) fn with_url(mut self, url: Url) -> Error {
@@ -101,7 +198,24 @@ impl Error {
 
     #[allow(unused)]
     pub(crate) fn into_io(self) -> io::Error {
-        io::Error::new(io::ErrorKind::Other, self)
+        let kind = self.find_io_kind();
+        io::Error::new(kind, self)
+    }
+
+    /// Walks the source chain looking for a cause whose `io::ErrorKind` is
+    /// known, so that `into_io` doesn't flatten everything to `Other`.
+    fn find_io_kind(&self) -> io::ErrorKind {
+        let mut source = self.source();
+        while let Some(err) = source {
+            if err.is::<TimedOut>() {
+                return io::ErrorKind::TimedOut;
+            }
+            if let Some(io_err) = err.downcast_ref::<io::Error>() {
+                return io_err.kind();
+            }
+            source = err.source();
+        }
+        io::ErrorKind::Other
     }
 }
 
@@ -118,6 +232,13 @@ impl fmt::Debug for Error {
             builder.field("source", source);
         }
 
+        #[cfg(feature = "backtrace")]
+        {
+            if let Some(ref backtrace) = self.inner.backtrace {
+                builder.field("backtrace", backtrace);
+            }
+        }
+
         builder.finish()
     }
 }
@@ -139,6 +260,7 @@ impl fmt::Display for Error {
         match self.inner.kind {
             Kind::Builder => f.write_str("builder error")?,
             Kind::Request => f.write_str("error sending request")?,
+            Kind::Connect => f.write_str("error connecting")?,
             Kind::Body => f.write_str("request or response body error")?,
             Kind::Decode => f.write_str("error decoding response body")?,
             Kind::Redirect => f.write_str("error following redirect")?,
@@ -173,6 +295,7 @@ impl StdError for Error {
 pub(crate) enum Kind {
     Builder,
     Request,
+    Connect,
     Redirect,
     Status(StatusCode),
     Body,
@@ -197,6 +320,10 @@ pub(crate) fn request<E: Into<BoxError>>(e: E) -> Error {
     Error::new(Kind::Request, Some(e))
 }
 
+pub(crate) fn connect<E: Into<BoxError>>(e: E) -> Error {
+    Error::new(Kind::Connect, Some(e))
+}
+
 pub(crate) fn loop_detected(url: Url) -> Error {
     Error::new(Kind::Redirect, Some("infinite redirect loop detected")).with_url(url)
 }
@@ -274,6 +401,41 @@ mod tests {
         assert_sync::<Error>();
     }
 
+    #[test]
+    fn test_is_connect() {
+        let err = super::connect("tcp connect refused");
+        assert!(err.is_connect());
+        assert!(!err.is_redirect());
+
+        let err = super::request("some other failure");
+        assert!(!err.is_connect());
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        // Connect failures and timeouts are retryable; builder, decode,
+        // status and redirect errors are not.
+        assert!(super::connect("tcp refused").is_retryable());
+        assert!(Error::new(Kind::Request, Some(TimedOut)).is_retryable());
+
+        assert!(!super::builder("bad url").is_retryable());
+        assert!(!super::decode("invalid json").is_retryable());
+        let url = Url::parse("http://example.com").unwrap();
+        assert!(!super::status_code(url, StatusCode::NOT_FOUND).is_retryable());
+        assert!(!super::loop_detected(Url::parse("http://example.com").unwrap()).is_retryable());
+    }
+
+    #[test]
+    fn test_downcast_ref() {
+        let err = super::body(TimedOut);
+        assert!(err.get_ref().is_some());
+        assert!(err.downcast_ref::<TimedOut>().is_some());
+        assert!(err.downcast_ref::<BlockingClientInAsyncContext>().is_none());
+
+        let source = err.into_source();
+        assert!(source.is_some());
+    }
+
     #[test]
     fn mem_size_of() {
         use std::mem::size_of;
@@ -294,6 +456,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn roundtrip_timeout_io_error_kind() {
+        let orig = Error::new(Kind::Request, Some(TimedOut));
+        // Convert reqwest::Error into an io::Error...
+        let io = orig.into_io();
+        assert_eq!(io.kind(), io::ErrorKind::TimedOut);
+        // ...and back, without losing the original kind...
+        let err = super::decode_io(io);
+        match err.inner.kind {
+            Kind::Request => (),
+            _ => panic!("{:?}", err),
+        }
+    }
+
     #[test]
     fn from_unknown_io_error() {
         let orig = io::Error::new(io::ErrorKind::Other, "orly");